@@ -10,6 +10,7 @@ pub enum MediaFrame {
 }
 
 /// A simplistic representation of an encoded MediaFrame, they just hold frame counters.
+#[derive(serde::Serialize, serde::Deserialize)]
 pub enum EncodedMediaFrame {
     Video(usize),
     Audio(usize),
@@ -18,17 +19,19 @@ pub enum EncodedMediaFrame {
 mod actors {
     use crate::{EncodedMediaFrame, MediaFrame};
     use anyhow::{bail, Error};
-    use log::info;
+    use log::{info, warn};
     use std::{thread, time::Duration};
-    use tonari_actor::{Actor, Context, Recipient};
+    use tonari_actor::{Actor, Context, LifecycleEvent, Recipient, Supervision};
 
     // Messages
     pub enum VideoCaptureMessage {
         Capture,
+        AdjustExposure(i32),
     }
 
     pub enum AudioCaptureMessage {
         Capture,
+        AdjustVolume(f32),
     }
 
     // Plumbing
@@ -53,15 +56,39 @@ mod actors {
         }
     }
 
+    /// Logs actor lifecycle events; a real supervisor could instead react to e.g. a
+    /// decoder stopping by telling the capture side to stop as well.
+    pub struct LifecycleMonitor;
+
+    impl Actor for LifecycleMonitor {
+        type Context = Context<Self::Message>;
+        type Error = Error;
+        type Message = LifecycleEvent;
+
+        fn name() -> &'static str {
+            "LifecycleMonitorActor"
+        }
+
+        fn handle(
+            &mut self,
+            _context: &mut Self::Context,
+            message: Self::Message,
+        ) -> Result<(), Self::Error> {
+            info!("Lifecycle event: {message:?}");
+            Ok(())
+        }
+    }
+
     // Egress pipeline
     pub struct VideoCapturer {
         frame_counter: usize,
+        exposure: i32,
         next: Recipient<MediaFrame>,
     }
 
     impl VideoCapturer {
         pub fn new(next: Recipient<MediaFrame>) -> Self {
-            Self { frame_counter: 0, next }
+            Self { frame_counter: 0, exposure: 0, next }
         }
     }
 
@@ -81,13 +108,20 @@ mod actors {
         ) -> Result<(), Self::Error> {
             match message {
                 VideoCaptureMessage::Capture => {
-                    // Simulate a video frame capture
-                    std::thread::sleep(Duration::from_millis(16));
-
+                    // Simulate a video frame capture, then schedule the next tick
+                    // instead of blocking this thread with a sleep.
                     self.next.send(MediaFrame::Video(self.frame_counter))?;
                     self.frame_counter += 1;
 
-                    context.myself.send(VideoCaptureMessage::Capture)?;
+                    context.schedule_once(
+                        Duration::from_millis(16),
+                        context.myself.clone(),
+                        VideoCaptureMessage::Capture,
+                    );
+                },
+                VideoCaptureMessage::AdjustExposure(exposure) => {
+                    info!("Adjusting video exposure to {exposure}");
+                    self.exposure = exposure;
                 },
             }
             Ok(())
@@ -132,16 +166,22 @@ mod actors {
 
             Ok(())
         }
+
+        fn on_error(&mut self, _context: &mut Self::Context, err: Self::Error) -> Supervision {
+            warn!("VideoEncoder dropping a frame and resuming: {err}");
+            Supervision::Resume
+        }
     }
 
     pub struct AudioCapturer {
         frame_counter: usize,
+        volume: f32,
         next: Recipient<MediaFrame>,
     }
 
     impl AudioCapturer {
         pub fn new(next: Recipient<MediaFrame>) -> Self {
-            Self { frame_counter: 0, next }
+            Self { frame_counter: 0, volume: 1.0, next }
         }
     }
 
@@ -161,13 +201,20 @@ mod actors {
         ) -> Result<(), Self::Error> {
             match message {
                 AudioCaptureMessage::Capture => {
-                    // Simulate an audio frame capture
-                    std::thread::sleep(Duration::from_millis(10));
-
+                    // Simulate an audio frame capture, then schedule the next tick
+                    // instead of blocking this thread with a sleep.
                     self.next.send(MediaFrame::Audio(self.frame_counter))?;
                     self.frame_counter += 1;
 
-                    context.myself.send(AudioCaptureMessage::Capture)?;
+                    context.schedule_once(
+                        Duration::from_millis(10),
+                        context.myself.clone(),
+                        AudioCaptureMessage::Capture,
+                    );
+                },
+                AudioCaptureMessage::AdjustVolume(volume) => {
+                    info!("Adjusting audio volume to {volume}");
+                    self.volume = volume;
                 },
             }
 
@@ -213,6 +260,11 @@ mod actors {
 
             Ok(())
         }
+
+        fn on_error(&mut self, _context: &mut Self::Context, err: Self::Error) -> Supervision {
+            warn!("AudioEncoder dropping a frame and resuming: {err}");
+            Supervision::Resume
+        }
     }
 
     pub struct NetworkSender {
@@ -449,13 +501,12 @@ fn main() -> Result<(), Error> {
             println!("The actor system is stopping, this is the preshutdown hook");
             Ok(())
         })),
-        ..SystemCallbacks::default()
     };
 
     let mut system = System::with_callbacks("main", system_callbacks);
 
-    // TODO - Add some extra "config" actors to adjust things like video capture exposure,
-    //        or playback volume.
+    let lifecycle_monitor_addr = system.spawn(LifecycleMonitor {})?;
+    system.subscribe_lifecycle(lifecycle_monitor_addr.recipient());
 
     // Handle Ctrl-C
     let shutdown_addr = system.spawn(ShutdownActor {})?;
@@ -477,9 +528,16 @@ fn main() -> Result<(), Error> {
         video_decode_addr.recipient(),
     ))?;
 
+    // Expose the receiving side over a real (if loopback) TCP connection, standing
+    // in for the "receive" process in a real two-process deployment.
+    let remote_addr: std::net::SocketAddr = "127.0.0.1:7878".parse().unwrap();
+    system.listen(remote_addr)?;
+    system.register_remote("NetworkReceiverActor", network_receiver_addr.recipient());
+
     // Sending side
+    let remote = system.connect(remote_addr)?;
     let network_sender_addr =
-        system.spawn(NetworkSender::new(network_receiver_addr.recipient()))?;
+        system.spawn(NetworkSender::new(remote.recipient("NetworkReceiverActor")))?;
 
     // I really want to initialize audio first.
     let audio_encode_addr =
@@ -495,6 +553,12 @@ fn main() -> Result<(), Error> {
     audio_capture_addr.send(AudioCaptureMessage::Capture)?;
     video_capture_addr.send(VideoCaptureMessage::Capture)?;
 
+    // Config actors to adjust video capture exposure or playback volume go through
+    // the priority lane, so they're applied promptly instead of queueing behind a
+    // backlog of frames.
+    video_capture_addr.send_priority(VideoCaptureMessage::AdjustExposure(3))?;
+    audio_capture_addr.send_priority(AudioCaptureMessage::AdjustVolume(0.8))?;
+
     // The display actor may spawn an OS window which in some cases must run
     // on the main application thread.
     let display_actor = VideoDisplay::new();
@@ -0,0 +1,30 @@
+use crate::Supervision;
+
+/// Defines the message-handling behaviour of an actor that is spawned and driven by
+/// a [`System`](crate::System).
+pub trait Actor: Sized + Send + 'static {
+    /// The context type threaded through [`handle`](Self::handle). Most actors can
+    /// use `Context<Self::Message>` as-is.
+    type Context;
+    /// The error type returned from [`handle`](Self::handle).
+    type Error;
+    /// The message type this actor's mailbox accepts.
+    type Message: Send + 'static;
+
+    /// A human-readable name used in logs and thread names.
+    fn name() -> &'static str;
+
+    /// Handles a single message taken off the actor's mailbox.
+    fn handle(
+        &mut self,
+        context: &mut Self::Context,
+        message: Self::Message,
+    ) -> Result<(), Self::Error>;
+
+    /// Decides what the runtime should do after `handle` returns `err`. The default
+    /// stops the actor, matching the previous behaviour of a failed `handle` call
+    /// ending the actor for good.
+    fn on_error(&mut self, _context: &mut Self::Context, _err: Self::Error) -> Supervision {
+        Supervision::Stop
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{error::Error, mailbox, mailbox::Mailbox, Actor};
+use crossbeam_channel::{Receiver, Sender};
+use std::sync::Arc;
+
+/// A typed handle to a spawned actor's mailbox, used to send it messages and to mint
+/// [`Recipient`]s that type-erase the concrete actor for its collaborators.
+pub struct Addr<A: Actor> {
+    mailbox: Arc<Mailbox<A::Message>>,
+}
+
+impl<A: Actor> Addr<A> {
+    pub(crate) fn new(mailbox: Arc<Mailbox<A::Message>>) -> Self {
+        Self { mailbox }
+    }
+
+    pub(crate) fn sender(&self) -> Sender<A::Message> {
+        self.mailbox.sender()
+    }
+
+    pub(crate) fn receiver(&self) -> Receiver<A::Message> {
+        self.mailbox.receiver()
+    }
+
+    pub(crate) fn priority_sender(&self) -> Sender<A::Message> {
+        self.mailbox.priority_sender()
+    }
+
+    pub(crate) fn priority_receiver(&self) -> Receiver<A::Message> {
+        self.mailbox.priority_receiver()
+    }
+
+    /// Sends a message to the actor's normal-priority lane.
+    pub fn send(&self, message: A::Message) -> Result<(), Error> {
+        mailbox::send(&self.sender(), message)
+    }
+
+    /// Sends a message to the actor's priority lane, which the runtime loop always
+    /// drains before taking the next normal-lane message. Use this for out-of-band
+    /// control messages (e.g. adjusting exposure or volume) that shouldn't queue
+    /// behind a backlog of data messages.
+    pub fn send_priority(&self, message: A::Message) -> Result<(), Error> {
+        mailbox::send(&self.priority_sender(), message)
+    }
+
+    /// Returns a type-erased [`Recipient`] for this actor's message type, so
+    /// collaborators don't need to know the concrete actor type.
+    pub fn recipient(&self) -> Recipient<A::Message> {
+        Recipient { inner: Inner::Local { sender: self.sender(), priority_sender: self.priority_sender() } }
+    }
+}
+
+impl<A: Actor> Clone for Addr<A> {
+    fn clone(&self) -> Self {
+        Self { mailbox: Arc::clone(&self.mailbox) }
+    }
+}
+
+impl<A: Actor> Default for Addr<A> {
+    /// Creates an address with a fresh mailbox that isn't bound to a running actor
+    /// yet. Messages sent to it queue up until it's handed to
+    /// `System::prepare(actor).with_addr(addr)`, which is handy when an actor needs
+    /// to know its own downstream `Recipient` before it's been constructed.
+    fn default() -> Self {
+        Self { mailbox: Arc::new(Mailbox::unbounded()) }
+    }
+}
+
+enum Inner<M> {
+    Local { sender: Sender<M>, priority_sender: Sender<M> },
+    /// Serializes and forwards the message over a transport connection; boxed so
+    /// that `Recipient<M>` doesn't need `M: Serialize` just to exist, only to be
+    /// constructed as a remote one in the first place (see `RemoteHandle::recipient`).
+    /// There's no remote priority lane yet, so `send_priority` just falls back to
+    /// this same closure.
+    Remote(Arc<dyn Fn(M) -> Result<(), Error> + Send + Sync>),
+}
+
+/// A type-erased handle that can send a particular message type to whichever actor
+/// it was obtained from, without naming that actor's type or caring whether it's
+/// local to this process or reachable over a [`RemoteHandle`](crate::RemoteHandle).
+pub struct Recipient<M> {
+    inner: Inner<M>,
+}
+
+impl<M> Recipient<M> {
+    pub(crate) fn from_local(sender: Sender<M>, priority_sender: Sender<M>) -> Self {
+        Self { inner: Inner::Local { sender, priority_sender } }
+    }
+
+    pub(crate) fn from_remote(send: impl Fn(M) -> Result<(), Error> + Send + Sync + 'static) -> Self {
+        Self { inner: Inner::Remote(Arc::new(send)) }
+    }
+
+    /// Sends a message to the actor's normal-priority lane.
+    pub fn send(&self, message: M) -> Result<(), Error> {
+        match &self.inner {
+            Inner::Local { sender, .. } => mailbox::send(sender, message),
+            Inner::Remote(send) => send(message),
+        }
+    }
+
+    /// Sends a message to the actor's priority lane. See
+    /// [`Addr::send_priority`] for when to reach for this.
+    pub fn send_priority(&self, message: M) -> Result<(), Error> {
+        match &self.inner {
+            Inner::Local { priority_sender, .. } => mailbox::send(priority_sender, message),
+            Inner::Remote(send) => send(message),
+        }
+    }
+}
+
+impl<M> Clone for Recipient<M> {
+    fn clone(&self) -> Self {
+        let inner = match &self.inner {
+            Inner::Local { sender, priority_sender } => {
+                Inner::Local { sender: sender.clone(), priority_sender: priority_sender.clone() }
+            },
+            Inner::Remote(send) => Inner::Remote(Arc::clone(send)),
+        };
+        Self { inner }
+    }
+}
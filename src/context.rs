@@ -0,0 +1,43 @@
+use crate::{schedule::ScheduleId, Recipient, SystemHandle};
+use std::time::Duration;
+
+/// The default context handed to [`Actor::handle`](crate::Actor::handle), giving the
+/// actor a way to message itself and a handle back into the [`System`](crate::System)
+/// that's running it.
+pub struct Context<M: Send + 'static> {
+    /// A `Recipient` pointing back at this actor's own mailbox.
+    pub myself: Recipient<M>,
+    /// A handle to the owning `System`, e.g. to request a shutdown.
+    pub system_handle: SystemHandle,
+}
+
+impl<M: Send + 'static> Context<M> {
+    /// Schedules `msg` to be sent to `recipient` once, after `delay` has elapsed.
+    /// Use this instead of `thread::sleep`-then-`send` inside `handle`, which blocks
+    /// the whole actor thread and drifts.
+    pub fn schedule_once<N: Send + 'static>(
+        &self,
+        delay: Duration,
+        recipient: Recipient<N>,
+        msg: N,
+    ) -> ScheduleId {
+        self.system_handle.shared.timer.schedule_once(delay, recipient, msg)
+    }
+
+    /// Schedules a message, produced anew by `factory` each time, to be sent to
+    /// `recipient` every `interval` starting after `initial`.
+    pub fn schedule_interval<N: Send + 'static>(
+        &self,
+        initial: Duration,
+        interval: Duration,
+        recipient: Recipient<N>,
+        factory: impl FnMut() -> N + Send + 'static,
+    ) -> ScheduleId {
+        self.system_handle.shared.timer.schedule_interval(initial, interval, recipient, factory)
+    }
+
+    /// Cancels a previously scheduled or recurring send.
+    pub fn cancel_schedule(&self, id: ScheduleId) {
+        self.system_handle.shared.timer.cancel(id);
+    }
+}
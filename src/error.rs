@@ -0,0 +1,37 @@
+use std::fmt;
+
+/// Errors produced by the actor runtime itself, as opposed to errors returned from
+/// an actor's own [`Actor::handle`](crate::Actor::handle) implementation.
+#[derive(Debug)]
+pub enum Error {
+    /// The target actor's mailbox has been dropped, i.e. the actor has stopped running.
+    Disconnected,
+    /// Sending to or receiving from a remote `System` failed at the transport level.
+    Io(std::io::Error),
+    /// A message failed to (de)serialize for a remote send.
+    Serialization(Box<bincode::ErrorKind>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Disconnected => write!(f, "recipient actor is no longer running"),
+            Error::Io(err) => write!(f, "remote transport error: {err}"),
+            Error::Serialization(err) => write!(f, "failed to (de)serialize a remote message: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<Box<bincode::ErrorKind>> for Error {
+    fn from(err: Box<bincode::ErrorKind>) -> Self {
+        Error::Serialization(err)
+    }
+}
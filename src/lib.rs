@@ -0,0 +1,25 @@
+//! A small actor framework: actors are plain structs driven by their own thread,
+//! communicating by sending messages through typed [`Addr`]/[`Recipient`] handles.
+
+mod actor;
+mod addr;
+mod context;
+mod error;
+mod lifecycle;
+mod mailbox;
+mod remote;
+mod schedule;
+mod supervision;
+mod system;
+
+pub use crate::{
+    actor::Actor,
+    addr::{Addr, Recipient},
+    context::Context,
+    error::Error,
+    lifecycle::LifecycleEvent,
+    remote::RemoteHandle,
+    schedule::ScheduleId,
+    supervision::Supervision,
+    system::{System, SystemCallbacks, SystemHandle},
+};
@@ -0,0 +1,17 @@
+/// A transition in a spawned actor's lifecycle, delivered to any `Recipient`
+/// registered via [`System::subscribe_lifecycle`](crate::System::subscribe_lifecycle).
+///
+/// Lets a collaborator react to another actor terminating instead of finding out the
+/// hard way when a `send` to it starts failing.
+#[derive(Debug, Clone)]
+pub enum LifecycleEvent {
+    /// An actor's runtime loop started.
+    ActorStarted { name: &'static str },
+    /// An actor's runtime loop stopped for good.
+    ActorStopped { name: &'static str },
+    /// An actor was restarted after `on_error` returned `Supervision::Restart`.
+    ActorRestarted { name: &'static str },
+    /// An actor's `handle` returned an error, formatted since actors have differing
+    /// `Error` types.
+    ActorFailed { name: &'static str, error: String },
+}
@@ -0,0 +1,40 @@
+use crate::error::Error;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+
+/// The two channels backing a single actor's mailbox: the normal lane, and a
+/// priority lane that the runtime loop always drains first so a control message
+/// (e.g. "change exposure") isn't stuck behind a backlog of data messages.
+pub(crate) struct Mailbox<M> {
+    sender: Sender<M>,
+    receiver: Receiver<M>,
+    priority_sender: Sender<M>,
+    priority_receiver: Receiver<M>,
+}
+
+impl<M> Mailbox<M> {
+    pub(crate) fn unbounded() -> Self {
+        let (sender, receiver) = unbounded();
+        let (priority_sender, priority_receiver) = unbounded();
+        Self { sender, receiver, priority_sender, priority_receiver }
+    }
+
+    pub(crate) fn sender(&self) -> Sender<M> {
+        self.sender.clone()
+    }
+
+    pub(crate) fn receiver(&self) -> Receiver<M> {
+        self.receiver.clone()
+    }
+
+    pub(crate) fn priority_sender(&self) -> Sender<M> {
+        self.priority_sender.clone()
+    }
+
+    pub(crate) fn priority_receiver(&self) -> Receiver<M> {
+        self.priority_receiver.clone()
+    }
+}
+
+pub(crate) fn send<M>(sender: &Sender<M>, message: M) -> Result<(), Error> {
+    sender.send(message).map_err(|_| Error::Disconnected)
+}
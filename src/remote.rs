@@ -0,0 +1,188 @@
+use crate::{Error, Recipient};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// A length-prefixed, bincode-encoded envelope carrying a message for an actor
+/// registered by name on the receiving end, since the wire can't carry Rust types.
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    actor_name: String,
+    payload: Vec<u8>,
+}
+
+fn write_frame(stream: &mut TcpStream, envelope: &Envelope) -> Result<(), Error> {
+    let framed = bincode::serialize(envelope)?;
+    stream.write_all(&(framed.len() as u32).to_be_bytes())?;
+    stream.write_all(&framed)?;
+    Ok(())
+}
+
+/// Reads one frame, or `None` on a clean disconnect.
+fn read_frame(stream: &mut TcpStream) -> std::io::Result<Option<Envelope>> {
+    let mut len_buf = [0u8; 4];
+    if let Err(err) = stream.read_exact(&mut len_buf) {
+        return if err.kind() == ErrorKind::UnexpectedEof { Ok(None) } else { Err(err) };
+    }
+
+    let mut framed = vec![0u8; u32::from_be_bytes(len_buf) as usize];
+    stream.read_exact(&mut framed)?;
+    let envelope = bincode::deserialize(&framed).map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?;
+    Ok(Some(envelope))
+}
+
+type Route = Arc<dyn Fn(&[u8]) -> Result<(), Error> + Send + Sync>;
+
+/// Maps actor names registered via
+/// [`System::register_remote`](crate::System::register_remote) to a closure that
+/// deserializes an incoming payload and forwards it to that actor's mailbox.
+#[derive(Default)]
+pub(crate) struct RemoteRegistry {
+    routes: Mutex<HashMap<String, Route>>,
+}
+
+impl RemoteRegistry {
+    pub(crate) fn register<M: DeserializeOwned + Send + 'static>(
+        &self,
+        name: impl Into<String>,
+        recipient: Recipient<M>,
+    ) {
+        let route: Route = Arc::new(move |payload: &[u8]| -> Result<(), Error> {
+            let message: M = bincode::deserialize(payload)?;
+            recipient.send(message)
+        });
+        self.routes.lock().unwrap().insert(name.into(), route);
+    }
+
+    fn dispatch(&self, envelope: Envelope) {
+        let route = self.routes.lock().unwrap().get(&envelope.actor_name).cloned();
+        match route {
+            Some(route) => {
+                if let Err(err) = route(&envelope.payload) {
+                    log::warn!("Failed routing a remote message to \"{}\": {err}", envelope.actor_name);
+                }
+            },
+            None => log::warn!("Received a remote message for unregistered actor \"{}\"", envelope.actor_name),
+        }
+    }
+}
+
+/// Accepts connections on `addr` on a background thread, handing each its own reader
+/// thread that routes incoming frames through `registry`. Returns the actual bound
+/// address, so a caller that passed port `0` can learn which port the OS picked.
+pub(crate) fn listen(addr: SocketAddr, registry: Arc<RemoteRegistry>) -> std::io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+
+    thread::Builder::new().name("tonari-actor-listener".to_owned()).spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    log::warn!("Failed accepting a remote connection: {err}");
+                    continue;
+                },
+            };
+            let registry = Arc::clone(&registry);
+
+            thread::spawn(move || loop {
+                match read_frame(&mut stream) {
+                    Ok(Some(envelope)) => registry.dispatch(envelope),
+                    Ok(None) => break,
+                    Err(err) => {
+                        log::warn!("Remote connection reader error: {err}");
+                        break;
+                    },
+                }
+            });
+        }
+    })?;
+
+    Ok(local_addr)
+}
+
+/// A connection to a remote `System`, obtained via
+/// [`System::connect`](crate::System::connect), used to mint [`Recipient`]s that send
+/// messages to actors registered there by name.
+pub struct RemoteHandle {
+    addr: SocketAddr,
+    stream: Arc<Mutex<TcpStream>>,
+}
+
+impl RemoteHandle {
+    pub(crate) fn connect(addr: SocketAddr) -> std::io::Result<Self> {
+        Ok(Self { addr, stream: Arc::new(Mutex::new(TcpStream::connect(addr)?)) })
+    }
+
+    /// Returns a `Recipient` that serializes each message it's sent and forwards it
+    /// over this connection to whichever actor was registered as `name` on the far
+    /// end, exactly like a `Recipient` for a local actor.
+    pub fn recipient<M: Serialize + Send + 'static>(&self, name: impl Into<String>) -> Recipient<M> {
+        let stream = Arc::clone(&self.stream);
+        let addr = self.addr;
+        let actor_name = name.into();
+
+        Recipient::from_remote(move |message: M| -> Result<(), Error> {
+            let payload = bincode::serialize(&message)?;
+            let envelope = Envelope { actor_name: actor_name.clone(), payload };
+
+            let mut stream = stream.lock().unwrap();
+            match write_frame(&mut stream, &envelope) {
+                Ok(()) => Ok(()),
+                Err(_) => {
+                    // The peer may have dropped the connection since we last wrote;
+                    // redial once before giving up, so a single send failure doesn't
+                    // permanently wedge this `Recipient`.
+                    *stream = TcpStream::connect(addr)?;
+                    write_frame(&mut stream, &envelope)
+                },
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::Shutdown, time::Duration};
+
+    fn listen_for_probe() -> (SocketAddr, crossbeam_channel::Receiver<u32>) {
+        let registry = Arc::new(RemoteRegistry::default());
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        registry.register("probe", Recipient::from_local(sender.clone(), sender));
+
+        let local_addr = listen("127.0.0.1:0".parse().unwrap(), registry).unwrap();
+        (local_addr, receiver)
+    }
+
+    #[test]
+    fn round_trips_a_message_over_localhost() {
+        let (local_addr, receiver) = listen_for_probe();
+        let handle = RemoteHandle::connect(local_addr).unwrap();
+
+        let recipient: Recipient<u32> = handle.recipient("probe");
+        recipient.send(7).unwrap();
+
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(500)).unwrap(), 7);
+    }
+
+    #[test]
+    fn redials_and_resends_after_the_connection_is_severed() {
+        let (local_addr, receiver) = listen_for_probe();
+        let handle = RemoteHandle::connect(local_addr).unwrap();
+
+        // Sever the handle's cached connection without tearing down the listener, so
+        // the next send has to redial before it can succeed.
+        handle.stream.lock().unwrap().shutdown(Shutdown::Both).unwrap();
+
+        let recipient: Recipient<u32> = handle.recipient("probe");
+        recipient.send(9).unwrap();
+
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(500)).unwrap(), 9);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{Error, Recipient};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Identifies a scheduled send so it can later be cancelled with
+/// [`Context::cancel_schedule`](crate::Context::cancel_schedule).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScheduleId(u64);
+
+/// A pending (or recurring) send, ordered by `deadline` so the earliest one sorts
+/// first out of the `BinaryHeap` (a max-heap, hence the reversed `Ord` impl below).
+struct Entry {
+    deadline: Instant,
+    id: ScheduleId,
+    interval: Option<Duration>,
+    fire: Box<dyn FnMut() -> Result<(), Error> + Send>,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.deadline.cmp(&self.deadline)
+    }
+}
+
+struct Shared {
+    heap: Mutex<BinaryHeap<Entry>>,
+    cancelled: Mutex<HashSet<ScheduleId>>,
+    /// Ids of recurring schedules currently popped out of `heap` for firing, so a
+    /// recurring schedule is always findable in at least one of `heap` or
+    /// `in_flight` from the moment it's pushed until it's fully done — closing the
+    /// race where `cancel()` would otherwise see neither and conclude (wrongly)
+    /// that it already fired for good. One-shots aren't tracked here: once popped
+    /// they're past cancelling.
+    in_flight: Mutex<HashSet<ScheduleId>>,
+    condvar: Condvar,
+    next_id: Mutex<u64>,
+}
+
+/// Owns the single background thread that delivers scheduled and recurring sends for
+/// a [`System`](crate::System). Parks on a `Condvar` until the next deadline, so an
+/// idle system with no schedules costs nothing beyond the thread itself.
+pub(crate) struct Timer {
+    shared: Arc<Shared>,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Self {
+        let shared = Arc::new(Shared {
+            heap: Mutex::new(BinaryHeap::new()),
+            cancelled: Mutex::new(HashSet::new()),
+            in_flight: Mutex::new(HashSet::new()),
+            condvar: Condvar::new(),
+            next_id: Mutex::new(0),
+        });
+
+        let worker = Arc::clone(&shared);
+        thread::Builder::new()
+            .name("tonari-actor-timer".to_owned())
+            .spawn(move || run(worker))
+            .expect("failed to spawn timer thread");
+
+        Self { shared }
+    }
+
+    fn schedule(
+        &self,
+        delay: Duration,
+        interval: Option<Duration>,
+        fire: Box<dyn FnMut() -> Result<(), Error> + Send>,
+    ) -> ScheduleId {
+        let id = {
+            let mut next_id = self.shared.next_id.lock().unwrap();
+            let id = ScheduleId(*next_id);
+            *next_id += 1;
+            id
+        };
+        let entry = Entry { deadline: Instant::now() + delay, id, interval, fire };
+
+        let mut heap = self.shared.heap.lock().unwrap();
+        // Only a newly-earliest deadline needs to wake the thread; otherwise it's
+        // already going to wake up in time for this one.
+        let wakes_thread_early = heap.peek().is_none_or(|earliest| entry.deadline < earliest.deadline);
+        heap.push(entry);
+        drop(heap);
+        if wakes_thread_early {
+            self.shared.condvar.notify_one();
+        }
+
+        id
+    }
+
+    /// Delivers `msg` to `recipient` once, after `delay` has elapsed.
+    pub(crate) fn schedule_once<M: Send + 'static>(
+        &self,
+        delay: Duration,
+        recipient: Recipient<M>,
+        msg: M,
+    ) -> ScheduleId {
+        let mut msg = Some(msg);
+        self.schedule(
+            delay,
+            None,
+            Box::new(move || recipient.send(msg.take().expect("one-shot schedule fired twice"))),
+        )
+    }
+
+    /// Delivers a message produced by `factory` to `recipient` every `interval`,
+    /// starting after `initial`. `factory` rather than a `Clone` message is needed
+    /// since most actor messages aren't `Clone`.
+    pub(crate) fn schedule_interval<M: Send + 'static>(
+        &self,
+        initial: Duration,
+        interval: Duration,
+        recipient: Recipient<M>,
+        mut factory: impl FnMut() -> M + Send + 'static,
+    ) -> ScheduleId {
+        self.schedule(initial, Some(interval), Box::new(move || recipient.send(factory())))
+    }
+
+    /// Cancels a pending or recurring schedule. A no-op if it already fired for
+    /// good (for one-shot schedules) or was already cancelled.
+    pub(crate) fn cancel(&self, id: ScheduleId) {
+        // Only remember the cancellation if the entry is still alive, i.e. still in
+        // the heap or, if recurring, currently being fired (`in_flight`): a one-shot that already
+        // fired for good will never be popped again, so inserting its id
+        // unconditionally would leak it in `cancelled` forever. Checking `in_flight`
+        // too (while still holding the heap lock) closes the gap where `run` briefly
+        // has the entry in neither set while it's firing a recurring schedule.
+        let heap = self.shared.heap.lock().unwrap();
+        let still_alive = heap.iter().any(|entry| entry.id == id) || self.shared.in_flight.lock().unwrap().contains(&id);
+        drop(heap);
+
+        if still_alive {
+            self.shared.cancelled.lock().unwrap().insert(id);
+        }
+    }
+}
+
+fn run(shared: Arc<Shared>) {
+    loop {
+        let heap = shared.heap.lock().unwrap();
+        let now = Instant::now();
+
+        match heap.peek() {
+            None => {
+                let _stale_guard = shared.condvar.wait(heap).unwrap();
+            },
+            Some(earliest) if earliest.deadline > now => {
+                let remaining = earliest.deadline - now;
+                let _stale_guard = shared.condvar.wait_timeout(heap, remaining).unwrap();
+            },
+            Some(_) => {
+                let mut heap = heap;
+                let mut entry = heap.pop().unwrap();
+                let id = entry.id;
+                // Mark a recurring entry in-flight before releasing the heap lock, so
+                // it's always findable in `heap` or `in_flight` — never neither — for
+                // the whole time a concurrent `cancel()` call might be looking for it.
+                if entry.interval.is_some() {
+                    shared.in_flight.lock().unwrap().insert(id);
+                }
+                drop(heap);
+
+                if !shared.cancelled.lock().unwrap().remove(&id) {
+                    if let Err(err) = (entry.fire)() {
+                        log::warn!("Dropping a scheduled send, its recipient is gone: {err}");
+                    } else if let Some(interval) = entry.interval {
+                        // Re-check cancellation right before requeuing: a `cancel()`
+                        // racing with the fire above would have found this entry
+                        // only in `in_flight`, so it's safe to trust here too.
+                        if !shared.cancelled.lock().unwrap().remove(&id) {
+                            // Computed from the scheduled deadline, not now, so a
+                            // slow fire doesn't drift the interval forward.
+                            entry.deadline += interval;
+                            shared.heap.lock().unwrap().push(entry);
+                        }
+                    }
+                }
+
+                shared.in_flight.lock().unwrap().remove(&id);
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Recipient;
+
+    fn recipient_and_receiver() -> (Recipient<u32>, crossbeam_channel::Receiver<u32>) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        (Recipient::from_local(sender.clone(), sender), receiver)
+    }
+
+    #[test]
+    fn fires_in_deadline_order_not_schedule_order() {
+        let timer = Timer::start();
+        let (recipient, receiver) = recipient_and_receiver();
+
+        timer.schedule_once(Duration::from_millis(50), recipient.clone(), 1);
+        timer.schedule_once(Duration::from_millis(10), recipient, 2);
+
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(500)).unwrap(), 2);
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(500)).unwrap(), 1);
+    }
+
+    #[test]
+    fn cancelling_a_pending_one_shot_stops_it_firing() {
+        let timer = Timer::start();
+        let (recipient, receiver) = recipient_and_receiver();
+
+        let id = timer.schedule_once(Duration::from_millis(20), recipient, 42);
+        timer.cancel(id);
+
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+    }
+
+    #[test]
+    fn cancelling_an_already_fired_one_shot_does_not_leak_its_id() {
+        let timer = Timer::start();
+        let (recipient, receiver) = recipient_and_receiver();
+
+        let id = timer.schedule_once(Duration::from_millis(1), recipient, 7);
+        receiver.recv_timeout(Duration::from_millis(200)).unwrap();
+
+        timer.cancel(id);
+
+        assert!(timer.shared.cancelled.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn cancelling_an_in_flight_recurring_schedule_is_not_lost() {
+        // `run` marks an entry `in_flight` for the window between popping it out of
+        // the heap and requeuing it for its next interval; a `cancel()` landing in
+        // that window must still take effect instead of being silently dropped.
+        let timer = Timer::start();
+        let id = ScheduleId(u64::MAX);
+        timer.shared.in_flight.lock().unwrap().insert(id);
+
+        timer.cancel(id);
+
+        assert!(timer.shared.cancelled.lock().unwrap().contains(&id));
+    }
+}
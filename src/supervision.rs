@@ -0,0 +1,15 @@
+/// What an actor's runtime loop should do after
+/// [`Actor::handle`](crate::Actor::handle) returns an error, as decided by
+/// [`Actor::on_error`](crate::Actor::on_error).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Supervision {
+    /// Keep the actor's current state and carry on with the next message.
+    Resume,
+    /// Discard the actor's state and replace it with a freshly constructed one,
+    /// keeping the same mailbox and `Addr`. Only possible for actors spawned with a
+    /// factory (e.g. via `System::spawn_with`); otherwise the runtime falls back to
+    /// `Stop` and logs a warning.
+    Restart,
+    /// Stop the actor for good.
+    Stop,
+}
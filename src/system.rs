@@ -0,0 +1,538 @@
+use crate::{
+    addr::Recipient,
+    lifecycle::LifecycleEvent,
+    mailbox::Mailbox,
+    remote::{self, RemoteHandle, RemoteRegistry},
+    schedule::Timer,
+    Actor, Addr, Context, Supervision,
+};
+use anyhow::Error;
+use log::{error, info, warn};
+use serde::de::DeserializeOwned;
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Hooks a [`System`] runs at well-known points in its lifecycle.
+#[derive(Default)]
+pub struct SystemCallbacks {
+    /// Invoked once, just before the system starts tearing down spawned actors.
+    pub preshutdown: Option<Box<dyn Fn() -> Result<(), Error> + Send>>,
+}
+
+pub(crate) struct SystemShared {
+    pub(crate) name: &'static str,
+    callbacks: Mutex<SystemCallbacks>,
+    shutting_down: AtomicBool,
+    pub(crate) timer: Timer,
+    lifecycle_subscribers: Mutex<Vec<Recipient<LifecycleEvent>>>,
+    remote: Arc<RemoteRegistry>,
+}
+
+/// A cheaply cloneable handle to a running [`System`], handed to every actor via
+/// [`Context::system_handle`](crate::Context::system_handle) so it can request a
+/// shutdown without needing to know about `System` itself.
+#[derive(Clone)]
+pub struct SystemHandle {
+    pub(crate) shared: Arc<SystemShared>,
+}
+
+impl SystemHandle {
+    /// Requests that the system shut down, running the `preshutdown` callback (once)
+    /// before any further mailboxes are drained.
+    pub fn shutdown(&self) -> Result<(), Error> {
+        if !self.shared.shutting_down.swap(true, Ordering::SeqCst) {
+            info!("System \"{}\" is shutting down", self.shared.name);
+            if let Some(preshutdown) = &self.shared.callbacks.lock().unwrap().preshutdown {
+                preshutdown()?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        self.shared.shutting_down.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn emit_lifecycle(&self, event: LifecycleEvent) {
+        let subscribers = self.shared.lifecycle_subscribers.lock().unwrap();
+        for subscriber in subscribers.iter() {
+            let _ = subscriber.send(event.clone());
+        }
+    }
+}
+
+/// Owns the actor threads spawned into it and coordinates their shutdown.
+pub struct System {
+    handle: SystemHandle,
+    join_handles: Vec<thread::JoinHandle<()>>,
+}
+
+impl System {
+    /// Creates a system with default (no-op) callbacks.
+    pub fn new(name: &'static str) -> Self {
+        Self::with_callbacks(name, SystemCallbacks::default())
+    }
+
+    /// Creates a system that runs the given `callbacks` at the relevant lifecycle
+    /// points.
+    pub fn with_callbacks(name: &'static str, callbacks: SystemCallbacks) -> Self {
+        let shared = Arc::new(SystemShared {
+            name,
+            callbacks: Mutex::new(callbacks),
+            shutting_down: AtomicBool::new(false),
+            timer: Timer::start(),
+            lifecycle_subscribers: Mutex::new(Vec::new()),
+            remote: Arc::new(RemoteRegistry::default()),
+        });
+        Self { handle: SystemHandle { shared }, join_handles: Vec::new() }
+    }
+
+    /// Returns a cloneable handle to this system.
+    pub fn handle(&self) -> SystemHandle {
+        self.handle.clone()
+    }
+
+    /// Registers `recipient` to receive every [`LifecycleEvent`] emitted by actors
+    /// spawned into this system from this point on, e.g. so a supervisor can react
+    /// to a downstream actor stopping instead of finding out from a failed `send`.
+    pub fn subscribe_lifecycle(&self, recipient: Recipient<LifecycleEvent>) {
+        self.handle.shared.lifecycle_subscribers.lock().unwrap().push(recipient);
+    }
+
+    /// Spawns `actor` onto its own thread with a freshly created mailbox, returning
+    /// an [`Addr`] to it. If the actor's `on_error` ever requests a `Restart`, it
+    /// simply falls back to `Stop`, since there's no factory to rebuild it from; use
+    /// [`System::spawn_with`] for restartable actors.
+    pub fn spawn<A: Actor<Context = Context<<A as Actor>::Message>>>(
+        &mut self,
+        actor: A,
+    ) -> Result<Addr<A>, Error>
+    where
+        A::Error: std::fmt::Display,
+    {
+        self.prepare(actor).run()
+    }
+
+    /// Spawns an actor built by repeatedly calling `factory`, which is invoked once
+    /// up front and again each time the actor's `on_error` requests a `Restart` —
+    /// the mailbox and `Addr` are preserved across restarts, only the actor's own
+    /// state is replaced.
+    pub fn spawn_with<A, F>(&mut self, mut factory: F) -> Result<Addr<A>, Error>
+    where
+        A: Actor<Context = Context<<A as Actor>::Message>>,
+        A::Error: std::fmt::Display,
+        F: FnMut() -> A + Send + 'static,
+    {
+        let actor = factory();
+        self.prepare(actor).supervised_by(factory).run()
+    }
+
+    /// Begins preparing an actor for spawning, allowing an existing [`Addr`] (created
+    /// up-front via `Addr::default()`) to be bound to it instead of a fresh one.
+    pub fn prepare<A: Actor<Context = Context<<A as Actor>::Message>>>(&mut self, actor: A) -> PreparedActor<'_, A> {
+        PreparedActor { system: self, actor, addr: None, factory: None }
+    }
+
+    /// Registers `recipient` to receive remote sends addressed to `name`, so a
+    /// `System::listen`ing peer can route incoming frames to it.
+    pub fn register_remote<M: DeserializeOwned + Send + 'static>(
+        &self,
+        name: impl Into<String>,
+        recipient: Recipient<M>,
+    ) {
+        self.handle.shared.remote.register(name, recipient);
+    }
+
+    /// Exposes actors registered via [`System::register_remote`] to remote peers
+    /// connecting to `addr`. Returns the actual bound address, so passing port `0`
+    /// to let the OS pick one is possible.
+    pub fn listen(&self, addr: SocketAddr) -> Result<SocketAddr, Error> {
+        Ok(remote::listen(addr, Arc::clone(&self.handle.shared.remote))?)
+    }
+
+    /// Connects to a remote `System` listening at `addr`, returning a handle that
+    /// mints `Recipient`s for actors it has registered by name.
+    pub fn connect(&self, addr: SocketAddr) -> Result<RemoteHandle, Error> {
+        Ok(RemoteHandle::connect(addr)?)
+    }
+}
+
+/// Builder returned by [`System::prepare`], allowing the caller to bind a
+/// pre-existing [`Addr`] before running the actor.
+pub struct PreparedActor<'system, A: Actor<Context = Context<<A as Actor>::Message>>> {
+    system: &'system mut System,
+    actor: A,
+    addr: Option<Addr<A>>,
+    factory: Option<Box<dyn FnMut() -> A + Send>>,
+}
+
+impl<'system, A: Actor<Context = Context<<A as Actor>::Message>>> PreparedActor<'system, A>
+where
+    A::Error: std::fmt::Display,
+{
+    /// Binds the actor to an already-created `Addr`, e.g. one handed out to
+    /// collaborators before the actor itself was constructed.
+    pub fn with_addr(mut self, addr: Addr<A>) -> Self {
+        self.addr = Some(addr);
+        self
+    }
+
+    /// Lets the runtime rebuild the actor from `factory` when `on_error` requests a
+    /// `Restart`, rather than falling back to `Stop`.
+    pub fn supervised_by(mut self, factory: impl FnMut() -> A + Send + 'static) -> Self {
+        self.factory = Some(Box::new(factory));
+        self
+    }
+
+    /// Spawns the actor onto its own thread and returns its `Addr`.
+    pub fn run(self) -> Result<Addr<A>, Error> {
+        let addr = self.addr.unwrap_or_else(|| Addr::new(Arc::new(Mailbox::unbounded())));
+        let system_handle = self.system.handle.clone();
+        let receiver = addr.receiver();
+        let priority_receiver = addr.priority_receiver();
+        let myself = Recipient::from_local(addr.sender(), addr.priority_sender());
+
+        let join_handle = thread::Builder::new().name(A::name().to_owned()).spawn(move || {
+            run_actor_loop(self.actor, self.factory, myself, system_handle, receiver, priority_receiver)
+        })?;
+        self.system.join_handles.push(join_handle);
+
+        Ok(addr)
+    }
+
+    /// Runs the actor on the calling thread, blocking until the system shuts down.
+    /// Intended for actors that must stay on the main thread, e.g. ones that own an
+    /// OS window.
+    pub fn run_and_block(self) -> Result<(), Error> {
+        let addr = self.addr.unwrap_or_else(|| Addr::new(Arc::new(Mailbox::unbounded())));
+        let system_handle = self.system.handle.clone();
+        let receiver = addr.receiver();
+        let priority_receiver = addr.priority_receiver();
+        let myself = Recipient::from_local(addr.sender(), addr.priority_sender());
+
+        run_actor_loop(self.actor, self.factory, myself, system_handle, receiver, priority_receiver);
+        for join_handle in self.system.join_handles.drain(..) {
+            let _ = join_handle.join();
+        }
+        Ok(())
+    }
+}
+
+/// What [`recv_prioritized`] found.
+enum Recv<M> {
+    Message(M),
+    /// Neither lane had anything within the timeout; the caller should recheck the
+    /// shutdown flag and try again.
+    Timeout,
+    /// Both lanes are gone. Since a `Recipient`/`Addr` always clones its normal and
+    /// priority senders together (see `Recipient::clone`), they disconnect together
+    /// too, so seeing one go is as good as seeing both.
+    Disconnected,
+}
+
+/// Blocks for the next message, always preferring one already waiting in
+/// `priority_receiver` over one in `receiver`.
+fn recv_prioritized<M>(
+    receiver: &crossbeam_channel::Receiver<M>,
+    priority_receiver: &crossbeam_channel::Receiver<M>,
+) -> Recv<M> {
+    use crossbeam_channel::{Select, TryRecvError};
+
+    // Drain the priority lane first, without blocking, so a burst of control
+    // messages never queues behind a single data message.
+    match priority_receiver.try_recv() {
+        Ok(message) => return Recv::Message(message),
+        Err(TryRecvError::Empty | TryRecvError::Disconnected) => {},
+    }
+
+    let mut select = Select::new();
+    select.recv(priority_receiver);
+    select.recv(receiver);
+
+    // `ready_timeout` (unlike `select_timeout`) only reports that some operation is
+    // ready without committing to completing it, so we're free to re-check the
+    // priority lane ourselves below instead of trusting whichever index it reports.
+    if select.ready_timeout(Duration::from_millis(100)).is_err() {
+        return Recv::Timeout;
+    }
+
+    // A priority message may have raced in after the non-blocking drain above but
+    // before `ready_timeout` returned; re-check here so it still wins over a
+    // normal-lane message that happened to be reported ready first.
+    match priority_receiver.try_recv() {
+        Ok(message) => return Recv::Message(message),
+        Err(TryRecvError::Empty) => {},
+        Err(TryRecvError::Disconnected) => return Recv::Disconnected,
+    }
+
+    match receiver.try_recv() {
+        Ok(message) => Recv::Message(message),
+        Err(TryRecvError::Empty) => Recv::Timeout,
+        Err(TryRecvError::Disconnected) => Recv::Disconnected,
+    }
+}
+
+fn run_actor_loop<A: Actor<Context = Context<<A as Actor>::Message>>>(
+    mut actor: A,
+    mut factory: Option<Box<dyn FnMut() -> A + Send>>,
+    myself: Recipient<A::Message>,
+    system_handle: SystemHandle,
+    receiver: crossbeam_channel::Receiver<A::Message>,
+    priority_receiver: crossbeam_channel::Receiver<A::Message>,
+) where
+    A::Error: std::fmt::Display,
+{
+    let mut context = Context { myself, system_handle: system_handle.clone() };
+    info!("Actor \"{}\" starting", A::name());
+    system_handle.emit_lifecycle(LifecycleEvent::ActorStarted { name: A::name() });
+
+    'outer: while !system_handle.is_shutting_down() {
+        let message = match recv_prioritized(&receiver, &priority_receiver) {
+            Recv::Message(message) => message,
+            Recv::Timeout => continue,
+            Recv::Disconnected => break,
+        };
+
+        if let Err(err) = actor.handle(&mut context, message) {
+            error!("Actor \"{}\" failed handling a message: {err}", A::name());
+            system_handle.emit_lifecycle(LifecycleEvent::ActorFailed { name: A::name(), error: err.to_string() });
+            match actor.on_error(&mut context, err) {
+                Supervision::Resume => {},
+                Supervision::Restart => match &mut factory {
+                    Some(factory) => {
+                        info!("Actor \"{}\" restarting", A::name());
+                        actor = factory();
+                        system_handle.emit_lifecycle(LifecycleEvent::ActorRestarted { name: A::name() });
+                    },
+                    None => {
+                        warn!(
+                            "Actor \"{}\" requested a restart but wasn't spawned with a factory, stopping instead",
+                            A::name()
+                        );
+                        break 'outer;
+                    },
+                },
+                Supervision::Stop => break 'outer,
+            }
+        }
+    }
+
+    warn!("Actor \"{}\" stopped", A::name());
+    system_handle.emit_lifecycle(LifecycleEvent::ActorStopped { name: A::name() });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drains_priority_lane_before_normal_lane() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (priority_sender, priority_receiver) = crossbeam_channel::unbounded();
+
+        sender.send("normal").unwrap();
+        priority_sender.send("priority").unwrap();
+
+        match recv_prioritized(&receiver, &priority_receiver) {
+            Recv::Message(message) => assert_eq!(message, "priority"),
+            _ => panic!("expected a message"),
+        }
+        match recv_prioritized(&receiver, &priority_receiver) {
+            Recv::Message(message) => assert_eq!(message, "normal"),
+            _ => panic!("expected a message"),
+        }
+    }
+
+    #[test]
+    fn reports_timeout_when_both_lanes_are_empty() {
+        let (_sender, receiver) = crossbeam_channel::unbounded::<()>();
+        let (_priority_sender, priority_receiver) = crossbeam_channel::unbounded::<()>();
+
+        assert!(matches!(recv_prioritized(&receiver, &priority_receiver), Recv::Timeout));
+    }
+
+    #[test]
+    fn reports_disconnected_once_both_senders_are_dropped() {
+        let (sender, receiver) = crossbeam_channel::unbounded::<()>();
+        let (priority_sender, priority_receiver) = crossbeam_channel::unbounded::<()>();
+        drop(sender);
+        drop(priority_sender);
+
+        assert!(matches!(recv_prioritized(&receiver, &priority_receiver), Recv::Disconnected));
+    }
+
+    #[derive(Debug)]
+    struct Boom;
+
+    impl std::fmt::Display for Boom {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "boom")
+        }
+    }
+
+    #[test]
+    fn restart_rebuilds_actor_state_while_keeping_its_mailbox() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        enum Msg {
+            Fail,
+            Probe(crossbeam_channel::Sender<u32>),
+        }
+
+        struct CountingActor {
+            generation: u32,
+        }
+
+        impl Actor for CountingActor {
+            type Context = Context<Msg>;
+            type Error = Boom;
+            type Message = Msg;
+
+            fn name() -> &'static str {
+                "counting-actor"
+            }
+
+            fn handle(&mut self, _context: &mut Context<Msg>, message: Msg) -> Result<(), Boom> {
+                match message {
+                    Msg::Fail => Err(Boom),
+                    Msg::Probe(sender) => {
+                        sender.send(self.generation).unwrap();
+                        Ok(())
+                    },
+                }
+            }
+
+            fn on_error(&mut self, _context: &mut Context<Msg>, _err: Boom) -> Supervision {
+                Supervision::Restart
+            }
+        }
+
+        let next_generation = Arc::new(AtomicU32::new(0));
+        let factory_generation = Arc::clone(&next_generation);
+
+        let mut system = System::new("test-restart");
+        let addr = system
+            .spawn_with(move || CountingActor { generation: factory_generation.fetch_add(1, Ordering::SeqCst) })
+            .unwrap();
+
+        addr.send(Msg::Fail).unwrap();
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        addr.send(Msg::Probe(sender)).unwrap();
+
+        let generation = receiver.recv_timeout(Duration::from_millis(500)).unwrap();
+        assert_eq!(generation, 1, "on_error's Restart should have rebuilt the actor exactly once via the factory");
+    }
+
+    #[test]
+    fn restart_without_a_factory_falls_back_to_stop() {
+        struct FailingActor;
+
+        impl Actor for FailingActor {
+            type Context = Context<()>;
+            type Error = Boom;
+            type Message = ();
+
+            fn name() -> &'static str {
+                "failing-actor"
+            }
+
+            fn handle(&mut self, _context: &mut Context<()>, _message: ()) -> Result<(), Boom> {
+                Err(Boom)
+            }
+
+            fn on_error(&mut self, _context: &mut Context<()>, _err: Boom) -> Supervision {
+                Supervision::Restart
+            }
+        }
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut system = System::new("test-fallback");
+        system.subscribe_lifecycle(Recipient::from_local(sender.clone(), sender));
+
+        let addr = system.spawn(FailingActor).unwrap();
+        addr.send(()).unwrap();
+
+        let stopped = loop {
+            match receiver.recv_timeout(Duration::from_millis(500)).unwrap() {
+                LifecycleEvent::ActorStopped { name } => break name == "failing-actor",
+                _ => continue,
+            }
+        };
+        assert!(stopped, "an actor restarted without a factory should stop instead");
+    }
+
+    #[test]
+    fn lifecycle_subscribers_see_every_transition_in_order() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        struct Dummy {
+            failures: Arc<AtomicU32>,
+        }
+
+        impl Actor for Dummy {
+            type Context = Context<()>;
+            type Error = Boom;
+            type Message = ();
+
+            fn name() -> &'static str {
+                "dummy-lifecycle"
+            }
+
+            fn handle(&mut self, _context: &mut Context<()>, _message: ()) -> Result<(), Boom> {
+                Err(Boom)
+            }
+
+            fn on_error(&mut self, _context: &mut Context<()>, _err: Boom) -> Supervision {
+                // Restart after the first failure, then stop after the second, so a
+                // single test run covers every transition exactly once.
+                if self.failures.fetch_add(1, Ordering::SeqCst) == 0 { Supervision::Restart } else { Supervision::Stop }
+            }
+        }
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut system = System::new("test-lifecycle");
+        system.subscribe_lifecycle(Recipient::from_local(sender.clone(), sender));
+
+        let failures = Arc::new(AtomicU32::new(0));
+        let addr = system.spawn_with(move || Dummy { failures: Arc::clone(&failures) }).unwrap();
+
+        addr.send(()).unwrap();
+        addr.send(()).unwrap();
+
+        let mut events = Vec::new();
+        loop {
+            match receiver.recv_timeout(Duration::from_millis(500)).unwrap() {
+                event @ LifecycleEvent::ActorStopped { name: "dummy-lifecycle" } => {
+                    events.push(event);
+                    break;
+                },
+                event => events.push(event),
+            }
+        }
+
+        let kinds: Vec<&str> = events
+            .iter()
+            .map(|event| match event {
+                LifecycleEvent::ActorStarted { .. } => "started",
+                LifecycleEvent::ActorFailed { .. } => "failed",
+                LifecycleEvent::ActorRestarted { .. } => "restarted",
+                LifecycleEvent::ActorStopped { .. } => "stopped",
+            })
+            .collect();
+        assert_eq!(kinds, ["started", "failed", "restarted", "failed", "stopped"]);
+
+        for event in &events {
+            if let LifecycleEvent::ActorFailed { error, .. } = event {
+                assert_eq!(error, "boom", "ActorFailed should carry handle's formatted error");
+            }
+        }
+    }
+}